@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use crate::errors::RequestAttemptError;
+use crate::history::HistoryListener;
+use crate::response::query_result::QueryResult;
+use crate::response::warning_handler::WarningHandler;
+use crate::statement::prepared_statement::PreparedStatement;
+
+/// Entry point for running queries against a cluster.
+#[derive(Clone)]
+pub struct Session {
+    pub(crate) warning_handler: Arc<dyn WarningHandler>,
+    pub(crate) history_listener: Arc<dyn HistoryListener>,
+}
+
+/// Executes `prepared` via `execute_once`, transparently re-preparing through `do_prepare` and
+/// retrying exactly once if the server reports - via
+/// [RequestAttemptError::StaleResultMetadata] - that the result metadata cached for `prepared`
+/// (used to deserialize `SKIP_METADATA` responses) has gone stale.
+///
+/// This is the retry behaviour the `SKIP_METADATA` optimization requires: the conversion layer
+/// (`NonErrorQueryResponse`) refuses to guess at column specs once it detects the cache is
+/// stale, and leaves re-preparing and retrying to the caller, here.
+pub(crate) async fn execute_with_metadata_reprepare<Exec, ExecFut, Prep, PrepFut>(
+    prepared: PreparedStatement,
+    mut execute_once: Exec,
+    mut do_prepare: Prep,
+) -> Result<QueryResult, RequestAttemptError>
+where
+    Exec: FnMut(PreparedStatement) -> ExecFut,
+    ExecFut:
+        std::future::Future<Output = (PreparedStatement, Result<QueryResult, RequestAttemptError>)>,
+    Prep: FnMut(&PreparedStatement) -> PrepFut,
+    PrepFut: std::future::Future<Output = Result<PreparedStatement, RequestAttemptError>>,
+{
+    let (prepared, result) = execute_once(prepared).await;
+    match result {
+        Err(RequestAttemptError::StaleResultMetadata) => {
+            let reprepared = do_prepare(&prepared).await?;
+            execute_once(reprepared).await.1
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use bytes::Bytes;
+
+    use super::*;
+
+    fn prepared(id: u8) -> PreparedStatement {
+        PreparedStatement::new(Bytes::from(vec![id]), "SELECT * FROM ks.t".to_owned(), None)
+    }
+
+    #[tokio::test]
+    async fn reprepares_once_on_stale_metadata_then_succeeds() {
+        let prepare_calls = AtomicUsize::new(0);
+        let execute_calls = AtomicUsize::new(0);
+
+        let result = execute_with_metadata_reprepare(
+            prepared(1),
+            |p| {
+                let attempt = execute_calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        (p, Err(RequestAttemptError::StaleResultMetadata))
+                    } else {
+                        (
+                            p,
+                            Ok(QueryResult::new_with_unknown_coordinator(
+                                None,
+                                None,
+                                Vec::new(),
+                                None,
+                            )),
+                        )
+                    }
+                }
+            },
+            |_prepared| {
+                prepare_calls.fetch_add(1, Ordering::SeqCst);
+                async { Ok(prepared(2)) }
+            },
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(prepare_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(execute_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_reprepare_on_other_errors() {
+        let prepare_calls = AtomicUsize::new(0);
+
+        let result = execute_with_metadata_reprepare(
+            prepared(1),
+            |p| async move { (p, Err(RequestAttemptError::NonfinishedPagingState)) },
+            |_prepared| {
+                prepare_calls.fetch_add(1, Ordering::SeqCst);
+                async { Ok(prepared(2)) }
+            },
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(RequestAttemptError::NonfinishedPagingState)
+        ));
+        assert_eq!(prepare_calls.load(Ordering::SeqCst), 0);
+    }
+}
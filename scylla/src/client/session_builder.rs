@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::client::session::Session;
+use crate::history::HistoryListener;
+use crate::response::warning_handler::WarningHandler;
+
+/// Builds a [`Session`] with the desired configuration.
+pub struct SessionBuilder {
+    known_nodes: Vec<String>,
+    warning_handler: Arc<dyn WarningHandler>,
+    history_listener: Arc<dyn HistoryListener>,
+}
+
+impl SessionBuilder {
+    pub fn new() -> Self {
+        Self {
+            known_nodes: Vec::new(),
+            warning_handler: Arc::new(()),
+            history_listener: Arc::new(()),
+        }
+    }
+
+    pub fn known_node(mut self, address: impl Into<String>) -> Self {
+        self.known_nodes.push(address.into());
+        self
+    }
+
+    /// Registers a [`WarningHandler`] invoked for every server warning received, in addition to
+    /// warnings still being attached to `QueryResult::warnings`. Defaults to a no-op handler.
+    pub fn warning_handler(mut self, warning_handler: Arc<dyn WarningHandler>) -> Self {
+        self.warning_handler = warning_handler;
+        self
+    }
+
+    /// Registers a [`HistoryListener`] invoked for every attempt made by the session. Defaults
+    /// to a no-op listener.
+    pub fn history_listener(mut self, history_listener: Arc<dyn HistoryListener>) -> Self {
+        self.history_listener = history_listener;
+        self
+    }
+
+    pub async fn build(self) -> Result<Session, crate::errors::RequestAttemptError> {
+        Ok(Session {
+            warning_handler: self.warning_handler,
+            history_listener: self.history_listener,
+        })
+    }
+}
+
+impl Default for SessionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,32 @@
+use scylla_cql::frame::response::ResponseKind;
+
+/// Failure of a single request attempt against a single coordinator.
+///
+/// A retry policy decides, based on the specific variant, whether the whole request should be
+/// retried - possibly against another coordinator.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RequestAttemptError {
+    /// The server replied with a response kind that isn't valid in this context.
+    #[error("Unexpected response from the server: {0:?}")]
+    UnexpectedResponse(ResponseKind),
+
+    /// A response converted as unpaged carried a nonfinished paging state, i.e. more pages were
+    /// left than the caller asked for. Internal driver bug, or a misbehaving server.
+    #[error(
+        "Internal driver API misuse or a server bug: nonfinished paging state \
+         would be discarded by `NonErrorQueryResponse::into_query_result`"
+    )]
+    NonfinishedPagingState,
+
+    /// The server honoured `SKIP_METADATA` on an EXECUTE, but the result metadata cached at
+    /// prepare time no longer matches the shape the rows were serialized with - the schema
+    /// most likely changed since this statement was prepared.
+    ///
+    /// Callers should re-prepare the statement and retry; see
+    /// `client::session::execute_with_metadata_reprepare`.
+    #[error(
+        "Cached result metadata is stale (schema likely changed since this statement was \
+         prepared): re-prepare and retry"
+    )]
+    StaleResultMetadata,
+}
@@ -0,0 +1,116 @@
+use bytes::Bytes;
+
+/// Describes the shape (column names) of a set of result rows.
+///
+/// Captured once, when a statement is prepared, and normally re-sent by the server on every
+/// `RESULT`/`Rows` frame - unless the `SKIP_METADATA` EXECUTE flag suppressed it, in which case
+/// the driver falls back to this cached copy (see `resolve_skipped_metadata` in
+/// `response::request_response`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultMetadata<'a> {
+    pub col_specs: Vec<ColumnSpec<'a>>,
+    /// Opaque id the server uses (protocol v5+ `result_metadata_id`) to let the driver detect
+    /// when cached metadata for a prepared statement has gone stale after a schema change.
+    id: Bytes,
+}
+
+impl<'a> ResultMetadata<'a> {
+    pub fn new(col_specs: Vec<ColumnSpec<'a>>, id: Bytes) -> Self {
+        Self { col_specs, id }
+    }
+
+    pub fn id(&self) -> &Bytes {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSpec<'a> {
+    pub name: std::borrow::Cow<'a, str>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SetKeyspace {
+    pub keyspace_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaChange {
+    pub change_type: String,
+    pub target: String,
+}
+
+/// A `RESULT`/`Rows` payload whose column metadata may or may not have been sent inline by the
+/// server.
+#[derive(Debug, Clone)]
+pub struct RawRows {
+    metadata: Option<ResultMetadata<'static>>,
+    /// Present when the server flagged that metadata changed (protocol v5 metadata-changed
+    /// flag / new `result_metadata_id`), even though this particular frame omitted it.
+    new_metadata_id: Option<Bytes>,
+    rows_count: usize,
+    serialized_rows: Bytes,
+}
+
+impl RawRows {
+    pub fn new(
+        metadata: Option<ResultMetadata<'static>>,
+        new_metadata_id: Option<Bytes>,
+        rows_count: usize,
+        serialized_rows: Bytes,
+    ) -> Self {
+        Self {
+            metadata,
+            new_metadata_id,
+            rows_count,
+            serialized_rows,
+        }
+    }
+
+    pub fn metadata(&self) -> Option<&ResultMetadata<'static>> {
+        self.metadata.as_ref()
+    }
+
+    pub fn new_metadata_id(&self) -> Option<&Bytes> {
+        self.new_metadata_id.as_ref()
+    }
+
+    pub fn rows_count(&self) -> usize {
+        self.rows_count
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn serialized_rows(&self) -> &Bytes {
+        &self.serialized_rows
+    }
+
+    /// Consumes `self` and returns it with `metadata` attached.
+    pub fn with_metadata(mut self, metadata: ResultMetadata<'static>) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+}
+
+pub(crate) enum ResultWithDeserializedMetadata {
+    Rows(
+        (
+            RawRows,
+            scylla_cql::frame::request::query::PagingStateResponse,
+        ),
+    ),
+    SetKeyspace(SetKeyspace),
+    SchemaChange(SchemaChange),
+    Void,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_with_inline_metadata_round_trip() {
+        let metadata = ResultMetadata::new(vec![], Bytes::from_static(b"id-1"));
+        let rows = RawRows::new(Some(metadata.clone()), None, 0, Bytes::new());
+        assert_eq!(rows.metadata(), Some(&metadata));
+    }
+}
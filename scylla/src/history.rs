@@ -0,0 +1,127 @@
+use std::fmt::Debug;
+
+use uuid::Uuid;
+
+use crate::response::Coordinator;
+
+/// Identifies a single logical request (potentially made up of several attempts and
+/// speculative executions) across the callbacks of a single [`HistoryListener`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RequestId(pub usize);
+
+/// Identifies a single attempt - one execution of a request against one coordinator - within
+/// the logical request it belongs to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AttemptId(pub usize);
+
+/// Observes the lifecycle of requests sent by the driver, attempt by attempt.
+///
+/// Unlike server-side tracing, a `HistoryListener` runs entirely in-process and does not need
+/// to be turned on per-query, which makes it suitable for always-on debugging of latency tails
+/// and retry storms: register one on the session to get a structured timeline of every attempt
+/// and speculative execution that made up a logical request.
+pub trait HistoryListener: Debug + Send + Sync {
+    /// Called once per logical request, before its first attempt, to mint the [`RequestId`]
+    /// that every other callback for this request - including the eventual
+    /// [`Self::log_request_completed`] - will be tagged with.
+    fn log_request_start(&self) -> RequestId;
+
+    /// Called when the driver starts a new attempt (including the first) to execute a request
+    /// against `coordinator`.
+    fn log_attempt_start(&self, coordinator: &Coordinator) -> AttemptId;
+
+    /// Called as soon as a non-error response to `attempt_id` has been parsed, before it is
+    /// converted into a `QueryResult`. `coordinator` is `None` when the attempt's coordinator
+    /// is not known at this point.
+    fn log_response_received(
+        &self,
+        attempt_id: AttemptId,
+        coordinator: Option<&Coordinator>,
+        tracing_id: Option<Uuid>,
+        warnings: &[String],
+    );
+
+    /// Called once the retry policy has decided whether a failed attempt will be retried.
+    fn log_retry_decided(&self, attempt_id: AttemptId, will_retry: bool);
+
+    /// Called when the whole logical request (all of its attempts and speculative executions)
+    /// has finished, successfully or not.
+    fn log_request_completed(&self, request_id: RequestId);
+}
+
+/// A [`HistoryListener`] that does nothing, used when no history tracking was configured.
+impl HistoryListener for () {
+    fn log_request_start(&self) -> RequestId {
+        RequestId(0)
+    }
+
+    fn log_attempt_start(&self, _coordinator: &Coordinator) -> AttemptId {
+        AttemptId(0)
+    }
+
+    fn log_response_received(
+        &self,
+        _attempt_id: AttemptId,
+        _coordinator: Option<&Coordinator>,
+        _tracing_id: Option<Uuid>,
+        _warnings: &[String],
+    ) {
+    }
+
+    fn log_retry_decided(&self, _attempt_id: AttemptId, _will_retry: bool) {}
+
+    fn log_request_completed(&self, _request_id: RequestId) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct RecordingHistoryListener {
+        responses_received: Mutex<Vec<(AttemptId, Option<Uuid>, Vec<String>)>>,
+    }
+
+    impl HistoryListener for RecordingHistoryListener {
+        fn log_request_start(&self) -> RequestId {
+            RequestId(0)
+        }
+
+        fn log_attempt_start(&self, _coordinator: &Coordinator) -> AttemptId {
+            AttemptId(0)
+        }
+
+        fn log_response_received(
+            &self,
+            attempt_id: AttemptId,
+            _coordinator: Option<&Coordinator>,
+            tracing_id: Option<Uuid>,
+            warnings: &[String],
+        ) {
+            self.responses_received.lock().unwrap().push((
+                attempt_id,
+                tracing_id,
+                warnings.to_vec(),
+            ));
+        }
+
+        fn log_retry_decided(&self, _attempt_id: AttemptId, _will_retry: bool) {}
+
+        fn log_request_completed(&self, _request_id: RequestId) {}
+    }
+
+    #[test]
+    fn log_response_received_captures_attempt_id_and_warnings() {
+        let listener = RecordingHistoryListener::default();
+
+        listener.log_response_received(AttemptId(7), None, None, &["batch too large".to_owned()]);
+
+        let recorded = listener.responses_received.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].0, AttemptId(7));
+        assert_eq!(recorded[0].1, None);
+        assert_eq!(recorded[0].2, vec!["batch too large".to_owned()]);
+    }
+}
@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use scylla_cql::frame::request::{execute, query};
+
+use crate::statement::prepared_statement::PreparedStatement;
+use crate::statement::query::Query;
+
+/// Builds the QUERY frame for `query`, together with the custom payload (if any) that must be
+/// attached to the request envelope alongside it - the protocol carries custom payloads at the
+/// envelope level, not inside the request body itself.
+pub(crate) fn build_query_request(
+    query: &Query,
+) -> (query::Query<'_>, Option<HashMap<String, Bytes>>) {
+    (query.to_query_frame(), query.get_custom_payload().cloned())
+}
+
+/// Builds the EXECUTE frame for `prepared`, together with its custom payload (if any).
+///
+/// The frame's `SKIP_METADATA` flag is set whenever [`PreparedStatement::cached_result_metadata`]
+/// is present, so repeated executions don't pay the cost of the server re-sending column specs
+/// on every page.
+pub(crate) fn build_execute_request(
+    prepared: &PreparedStatement,
+) -> (execute::Execute<'_>, Option<HashMap<String, Bytes>>) {
+    (
+        prepared.to_execute_frame(),
+        prepared.get_custom_payload().cloned(),
+    )
+}
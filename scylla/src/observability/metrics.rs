@@ -0,0 +1,24 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Driver-internal counters, exposed so applications can wire them into their own metrics
+/// backend without the driver taking a dependency on any particular one.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    server_warnings: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called once per server warning received; see
+    /// [`MetricsWarningHandler`](crate::response::warning_handler::MetricsWarningHandler).
+    pub(crate) fn inc_server_warnings(&self) {
+        self.server_warnings.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_server_warnings(&self) -> u64 {
+        self.server_warnings.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use uuid::Uuid;
+
+use crate::frame::response::result;
+use crate::response::Coordinator;
+
+/// Result of a single request: the rows returned by a `SELECT`, or just an acknowledgement for
+/// writes/DDL, together with the diagnostic metadata the server attached to the response.
+#[derive(Debug, Clone)]
+pub struct QueryResult {
+    raw_rows: Option<result::RawRows>,
+    tracing_id: Option<Uuid>,
+    warnings: Vec<String>,
+    custom_payload: Option<HashMap<String, Bytes>>,
+    coordinator: Option<Coordinator>,
+}
+
+impl QueryResult {
+    pub(crate) fn new(
+        coordinator: Coordinator,
+        raw_rows: Option<result::RawRows>,
+        tracing_id: Option<Uuid>,
+        warnings: Vec<String>,
+        custom_payload: Option<HashMap<String, Bytes>>,
+    ) -> Self {
+        Self {
+            raw_rows,
+            tracing_id,
+            warnings,
+            custom_payload,
+            coordinator: Some(coordinator),
+        }
+    }
+
+    /// See [Self::new]. Used when the request couldn't be attributed to a single coordinator
+    /// (e.g. it was never actually sent).
+    pub(crate) fn new_with_unknown_coordinator(
+        raw_rows: Option<result::RawRows>,
+        tracing_id: Option<Uuid>,
+        warnings: Vec<String>,
+        custom_payload: Option<HashMap<String, Bytes>>,
+    ) -> Self {
+        Self {
+            raw_rows,
+            tracing_id,
+            warnings,
+            custom_payload,
+            coordinator: None,
+        }
+    }
+
+    /// The coordinator that served this request, if known.
+    pub fn coordinator(&self) -> Option<&Coordinator> {
+        self.coordinator.as_ref()
+    }
+
+    /// The raw row payload, if this was a response to a query that returns rows.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn raw_rows(&self) -> Option<&result::RawRows> {
+        self.raw_rows.as_ref()
+    }
+
+    /// Id of the query trace for this request, if tracing was enabled.
+    pub fn tracing_id(&self) -> Option<Uuid> {
+        self.tracing_id
+    }
+
+    /// Warnings the server attached to this response (e.g. "batch too large", "tombstone
+    /// overwhelm").
+    pub fn warnings(&self) -> impl Iterator<Item = &str> {
+        self.warnings.iter().map(String::as_str)
+    }
+
+    /// Custom payload the server attached to this response, if any.
+    ///
+    /// This is the standard CQL v4+ custom-payload mechanism used by server-side extensions
+    /// and proxies to attach out-of-band key/value data to a response.
+    pub fn custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.custom_payload.as_ref()
+    }
+}
@@ -10,14 +10,16 @@ use uuid::Uuid;
 
 use crate::errors::RequestAttemptError;
 use crate::frame::response::{self, result};
-use crate::response::Coordinator;
+use crate::history::{AttemptId, HistoryListener};
 use crate::response::query_result::QueryResult;
+use crate::response::warning_handler::WarningHandler;
+use crate::response::Coordinator;
 
 pub(crate) struct QueryResponse {
     pub(crate) response: ResponseWithDeserializedMetadata,
     pub(crate) tracing_id: Option<Uuid>,
     pub(crate) warnings: Vec<String>,
-    // This is not exposed to user (yet?)
+    // Server-attached custom payload, forwarded to the user via `QueryResult::custom_payload`.
     pub(crate) custom_payload: Option<HashMap<String, Bytes>>,
 }
 
@@ -26,6 +28,7 @@ pub(crate) struct NonErrorQueryResponse {
     pub(crate) response: NonErrorResponseWithDeserializedMetadata,
     pub(crate) tracing_id: Option<Uuid>,
     pub(crate) warnings: Vec<String>,
+    pub(crate) custom_payload: Option<HashMap<String, Bytes>>,
 }
 
 impl QueryResponse {
@@ -36,6 +39,7 @@ impl QueryResponse {
             response: self.response.into_non_error_response()?,
             tracing_id: self.tracing_id,
             warnings: self.warnings,
+            custom_payload: self.custom_payload,
         })
     }
 }
@@ -59,19 +63,46 @@ impl NonErrorQueryResponse {
         }
     }
 
+    /// `cached_result_metadata` is the metadata captured when the executed prepared statement
+    /// was prepared. It is only consulted when the server actually skipped result metadata
+    /// (the `SKIP_METADATA` EXECUTE flag was set and honoured), in which case `rs` carries no
+    /// metadata of its own and needs it to deserialize rows.
+    ///
+    /// Every warning on the response is also reported to `warning_handler`, in addition to
+    /// being attached to the returned [QueryResult] as before. A response-received event for
+    /// `attempt_id` is reported to `history_listener`.
     fn into_query_result_and_paging_state_with_maybe_unknown_coordinator(
         self,
         request_coordinator: Option<Coordinator>,
+        cached_result_metadata: Option<&result::ResultMetadata<'static>>,
+        warning_handler: &dyn WarningHandler,
+        attempt_id: AttemptId,
+        history_listener: &dyn HistoryListener,
     ) -> Result<(QueryResult, PagingStateResponse), RequestAttemptError> {
         let Self {
             response,
             tracing_id,
             warnings,
+            custom_payload,
         } = self;
+
+        for warning in &warnings {
+            warning_handler.handle_warning(request_coordinator.as_ref(), tracing_id, warning);
+        }
+        history_listener.log_response_received(
+            attempt_id,
+            request_coordinator.as_ref(),
+            tracing_id,
+            &warnings,
+        );
+
         let (raw_rows, paging_state_response) = match response {
             NonErrorResponseWithDeserializedMetadata::Result(
                 result::ResultWithDeserializedMetadata::Rows((rs, paging_state_response)),
-            ) => (Some(rs), paging_state_response),
+            ) => {
+                let rs = resolve_skipped_metadata(rs, cached_result_metadata)?;
+                (Some(rs), paging_state_response)
+            }
             NonErrorResponseWithDeserializedMetadata::Result(_) => {
                 (None, PagingStateResponse::NoMorePages)
             }
@@ -84,30 +115,56 @@ impl NonErrorQueryResponse {
 
         Ok((
             match request_coordinator {
-                Some(coordinator) => QueryResult::new(coordinator, raw_rows, tracing_id, warnings),
-                None => QueryResult::new_with_unknown_coordinator(raw_rows, tracing_id, warnings),
+                Some(coordinator) => {
+                    QueryResult::new(coordinator, raw_rows, tracing_id, warnings, custom_payload)
+                }
+                None => QueryResult::new_with_unknown_coordinator(
+                    raw_rows,
+                    tracing_id,
+                    warnings,
+                    custom_payload,
+                ),
             },
             paging_state_response,
         ))
     }
 
     /// Converts [NonErrorQueryResponse] into [QueryResult] and the associated [PagingStateResponse].
+    ///
+    /// `cached_result_metadata` must be supplied whenever the originating EXECUTE set the
+    /// `SKIP_METADATA` flag, so that rows can still be deserialized.
     pub(crate) fn into_query_result_and_paging_state(
         self,
         request_coordinator: Coordinator,
+        cached_result_metadata: Option<&result::ResultMetadata<'static>>,
+        warning_handler: &dyn WarningHandler,
+        attempt_id: AttemptId,
+        history_listener: &dyn HistoryListener,
     ) -> Result<(QueryResult, PagingStateResponse), RequestAttemptError> {
-        self.into_query_result_and_paging_state_with_maybe_unknown_coordinator(Some(
-            request_coordinator,
-        ))
+        self.into_query_result_and_paging_state_with_maybe_unknown_coordinator(
+            Some(request_coordinator),
+            cached_result_metadata,
+            warning_handler,
+            attempt_id,
+            history_listener,
+        )
     }
 
     fn into_query_result_with_maybe_unknown_coordinator(
         self,
         request_coordinator: Option<Coordinator>,
+        cached_result_metadata: Option<&result::ResultMetadata<'static>>,
+        warning_handler: &dyn WarningHandler,
+        attempt_id: AttemptId,
+        history_listener: &dyn HistoryListener,
     ) -> Result<QueryResult, RequestAttemptError> {
         let (result, paging_state) = self
             .into_query_result_and_paging_state_with_maybe_unknown_coordinator(
                 request_coordinator,
+                cached_result_metadata,
+                warning_handler,
+                attempt_id,
+                history_listener,
             )?;
 
         if !paging_state.finished() {
@@ -123,11 +180,24 @@ impl NonErrorQueryResponse {
 
     /// Converts [NonErrorQueryResponse] into [QueryResult]. Because it's intended to be used together with unpaged queries,
     /// it asserts that the associated [PagingStateResponse] is <finished> (says that there are no more pages left).
+    ///
+    /// `cached_result_metadata` must be supplied whenever the originating EXECUTE set the
+    /// `SKIP_METADATA` flag, so that rows can still be deserialized.
     pub(crate) fn into_query_result(
         self,
         request_coordinator: Coordinator,
+        cached_result_metadata: Option<&result::ResultMetadata<'static>>,
+        warning_handler: &dyn WarningHandler,
+        attempt_id: AttemptId,
+        history_listener: &dyn HistoryListener,
     ) -> Result<QueryResult, RequestAttemptError> {
-        self.into_query_result_with_maybe_unknown_coordinator(Some(request_coordinator))
+        self.into_query_result_with_maybe_unknown_coordinator(
+            Some(request_coordinator),
+            cached_result_metadata,
+            warning_handler,
+            attempt_id,
+            history_listener,
+        )
     }
 
     /// The same as [Self::into_query_result()], but not omitting the [Coordinator].
@@ -136,8 +206,51 @@ impl NonErrorQueryResponse {
     /// See [QueryResult::new_with_unknown_coordinator]
     pub(crate) fn into_query_result_with_unknown_coordinator(
         self,
+        cached_result_metadata: Option<&result::ResultMetadata<'static>>,
+        warning_handler: &dyn WarningHandler,
+        attempt_id: AttemptId,
+        history_listener: &dyn HistoryListener,
     ) -> Result<QueryResult, RequestAttemptError> {
-        self.into_query_result_with_maybe_unknown_coordinator(None)
+        self.into_query_result_with_maybe_unknown_coordinator(
+            None,
+            cached_result_metadata,
+            warning_handler,
+            attempt_id,
+            history_listener,
+        )
+    }
+}
+
+/// Resolves a `RESULT`/`Rows` payload that may have been sent without its own column metadata
+/// (the `SKIP_METADATA` EXECUTE flag was honoured by the server).
+///
+/// If `rs` already carries inline metadata, it's returned unchanged. Otherwise this is the
+/// `SKIP_METADATA` path, and there are two cases, distinguished by `rs.new_metadata_id()`:
+/// * `None` - the server has nothing new to report, so the shape is exactly the one
+///   `cached_result_metadata` was captured from (at prepare time); the cached copy is attached
+///   and rows can be deserialized as usual. This is the common case this feature exists for.
+/// * `Some(new_id)` - the server is flagging that the metadata changed to `new_id`. If our cache
+///   already reflects that id, it's attached and used as above; otherwise the schema changed
+///   since this statement was prepared and our cache can't be trusted.
+///
+/// Whenever there's no cached metadata to fall back on - in either case above - this returns
+/// [RequestAttemptError::StaleResultMetadata] rather than risk mis-parsing rows against the
+/// wrong column specs, so the caller can re-prepare and retry (see
+/// `client::session::execute_with_metadata_reprepare`).
+fn resolve_skipped_metadata(
+    rs: result::RawRows,
+    cached_result_metadata: Option<&result::ResultMetadata<'static>>,
+) -> Result<result::RawRows, RequestAttemptError> {
+    if rs.metadata().is_some() {
+        return Ok(rs);
+    }
+
+    match (rs.new_metadata_id(), cached_result_metadata) {
+        (None, Some(cached)) => Ok(rs.with_metadata(cached.clone())),
+        (Some(new_id), Some(cached)) if cached.id() == new_id => {
+            Ok(rs.with_metadata(cached.clone()))
+        }
+        _ => Err(RequestAttemptError::StaleResultMetadata),
     }
 }
 
@@ -150,3 +263,90 @@ pub(crate) enum NonErrorAuthResponse {
     AuthChallenge(response::authenticate::AuthChallenge),
     AuthSuccess(response::authenticate::AuthSuccess),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(id: &'static [u8]) -> result::ResultMetadata<'static> {
+        result::ResultMetadata::new(vec![], Bytes::from_static(id))
+    }
+
+    #[test]
+    fn inline_metadata_is_left_untouched() {
+        let rows = result::RawRows::new(Some(metadata(b"id-1")), None, 0, Bytes::new());
+        let resolved = resolve_skipped_metadata(rows, None).unwrap();
+        assert_eq!(resolved.metadata(), Some(&metadata(b"id-1")));
+    }
+
+    #[test]
+    fn skipped_metadata_with_no_reported_change_falls_back_to_cache() {
+        // The common SKIP_METADATA case: the server honoured the flag and has nothing new to
+        // report, so `metadata` and `new_metadata_id` are both unset.
+        let rows = result::RawRows::new(None, None, 3, Bytes::new());
+        let cached = metadata(b"id-1");
+        let resolved = resolve_skipped_metadata(rows, Some(&cached)).unwrap();
+        assert_eq!(resolved.metadata(), Some(&cached));
+    }
+
+    #[test]
+    fn skipped_metadata_with_no_reported_change_and_no_cache_is_reported_as_stale() {
+        let rows = result::RawRows::new(None, None, 3, Bytes::new());
+        let err = resolve_skipped_metadata(rows, None).unwrap_err();
+        assert!(matches!(err, RequestAttemptError::StaleResultMetadata));
+    }
+
+    #[test]
+    fn skipped_metadata_falls_back_to_matching_cache_after_reported_change() {
+        let rows = result::RawRows::new(None, Some(Bytes::from_static(b"id-1")), 3, Bytes::new());
+        let cached = metadata(b"id-1");
+        let resolved = resolve_skipped_metadata(rows, Some(&cached)).unwrap();
+        assert_eq!(resolved.metadata(), Some(&cached));
+    }
+
+    #[test]
+    fn skipped_metadata_with_mismatched_cache_is_reported_as_stale() {
+        let rows = result::RawRows::new(None, Some(Bytes::from_static(b"id-2")), 3, Bytes::new());
+        let cached = metadata(b"id-1");
+        let err = resolve_skipped_metadata(rows, Some(&cached)).unwrap_err();
+        assert!(matches!(err, RequestAttemptError::StaleResultMetadata));
+    }
+
+    #[test]
+    fn skipped_metadata_with_reported_change_and_no_cache_is_reported_as_stale() {
+        let rows = result::RawRows::new(None, Some(Bytes::from_static(b"id-1")), 3, Bytes::new());
+        let err = resolve_skipped_metadata(rows, None).unwrap_err();
+        assert!(matches!(err, RequestAttemptError::StaleResultMetadata));
+    }
+
+    /// Stands in for the proxy-based integration test the request asked for (set
+    /// `SKIP_METADATA`, verify rows still deserialize against cached column specs), which would
+    /// otherwise require a full transport layer this snapshot doesn't have: drives the actual
+    /// `NonErrorQueryResponse` conversion, as it would be called with a real `SKIP_METADATA`
+    /// EXECUTE response, and checks the cached metadata ends up on the resulting `QueryResult`.
+    #[test]
+    fn skip_metadata_happy_path_attaches_cached_metadata() {
+        let cached = metadata(b"id-1");
+        let rows = result::RawRows::new(None, None, 2, Bytes::from_static(b"row bytes"));
+        let response = NonErrorQueryResponse {
+            response: NonErrorResponseWithDeserializedMetadata::Result(
+                result::ResultWithDeserializedMetadata::Rows((
+                    rows,
+                    PagingStateResponse::NoMorePages,
+                )),
+            ),
+            tracing_id: None,
+            warnings: Vec::new(),
+            custom_payload: None,
+        };
+
+        let result = response
+            .into_query_result_with_unknown_coordinator(Some(&cached), &(), AttemptId(0), &())
+            .unwrap();
+
+        assert_eq!(
+            result.raw_rows().and_then(|rs| rs.metadata()),
+            Some(&cached)
+        );
+    }
+}
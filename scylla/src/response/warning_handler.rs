@@ -0,0 +1,83 @@
+use uuid::Uuid;
+
+use crate::response::Coordinator;
+
+/// Receives server-side warnings as they are parsed out of CQL responses.
+///
+/// Server warnings (e.g. "batch too large", "tombstone overwhelm") are the primary
+/// in-band signal for detecting anti-patterns, but by default they are only attached
+/// to [`QueryResult`](crate::response::query_result::QueryResult) as a flat list of
+/// strings. Implement this trait and register it on the
+/// [`Session`](crate::client::session::Session) to count, log, or assert on them instead.
+pub trait WarningHandler: Send + Sync {
+    /// Called once per warning string present on a response, alongside the coordinator
+    /// that produced it (if known) and the response's tracing id (if tracing was enabled).
+    fn handle_warning(
+        &self,
+        coordinator: Option<&Coordinator>,
+        tracing_id: Option<Uuid>,
+        warning: &str,
+    );
+}
+
+/// A [`WarningHandler`] that does nothing, preserving the driver's historical behaviour
+/// of only surfacing warnings through `QueryResult::warnings`.
+impl WarningHandler for () {
+    fn handle_warning(
+        &self,
+        _coordinator: Option<&Coordinator>,
+        _tracing_id: Option<Uuid>,
+        _warning: &str,
+    ) {
+    }
+}
+
+/// A [`WarningHandler`] that increments a metric every time a warning is received, in
+/// addition to the warning still being attached to `QueryResult::warnings`.
+///
+/// Useful for alerting on the rate of anti-pattern warnings (oversized batches, large
+/// partitions, ...) without having to inspect every `QueryResult` individually.
+#[derive(Debug, Clone)]
+pub struct MetricsWarningHandler {
+    metrics: std::sync::Arc<crate::observability::metrics::Metrics>,
+}
+
+impl MetricsWarningHandler {
+    pub fn new(metrics: std::sync::Arc<crate::observability::metrics::Metrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+impl WarningHandler for MetricsWarningHandler {
+    fn handle_warning(
+        &self,
+        _coordinator: Option<&Coordinator>,
+        _tracing_id: Option<Uuid>,
+        _warning: &str,
+    ) {
+        self.metrics.inc_server_warnings();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::metrics::Metrics;
+
+    #[test]
+    fn noop_handler_does_nothing() {
+        // Just exercises the no-op impl; there's nothing to assert beyond "it doesn't panic".
+        ().handle_warning(None, None, "batch too large");
+    }
+
+    #[test]
+    fn metrics_handler_increments_server_warnings() {
+        let metrics = std::sync::Arc::new(Metrics::new());
+        let handler = MetricsWarningHandler::new(metrics.clone());
+
+        handler.handle_warning(None, None, "batch too large");
+        handler.handle_warning(None, None, "tombstone overwhelm");
+
+        assert_eq!(metrics.get_server_warnings(), 2);
+    }
+}
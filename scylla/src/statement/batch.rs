@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+
+use crate::statement::query::Query;
+
+/// A set of statements executed together as one batch.
+#[derive(Debug, Clone, Default)]
+pub struct Batch {
+    pub(crate) statements: Vec<Query>,
+    custom_payload: Option<HashMap<String, Bytes>>,
+}
+
+impl Batch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn append_statement(&mut self, query: Query) {
+        self.statements.push(query);
+    }
+
+    /// Attaches a custom payload to be sent to the server alongside this batch. See
+    /// `Query::set_custom_payload` for the general mechanism.
+    pub fn set_custom_payload(&mut self, custom_payload: Option<HashMap<String, Bytes>>) {
+        self.custom_payload = custom_payload;
+    }
+
+    pub fn get_custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.custom_payload.as_ref()
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use scylla_cql::frame::request::execute;
+
+use crate::frame::response::result;
+
+/// A statement prepared on the cluster ahead of time, executed by its prepared id rather than
+/// its text.
+#[derive(Debug, Clone)]
+pub struct PreparedStatement {
+    id: Bytes,
+    contents: String,
+    cached_result_metadata: Option<Arc<result::ResultMetadata<'static>>>,
+    custom_payload: Option<HashMap<String, Bytes>>,
+}
+
+impl PreparedStatement {
+    pub(crate) fn new(
+        id: Bytes,
+        contents: String,
+        cached_result_metadata: Option<Arc<result::ResultMetadata<'static>>>,
+    ) -> Self {
+        Self {
+            id,
+            contents,
+            cached_result_metadata,
+            custom_payload: None,
+        }
+    }
+
+    pub(crate) fn id(&self) -> &Bytes {
+        &self.id
+    }
+
+    /// The CQL text this statement was prepared from - used to re-prepare it if the server
+    /// ever reports that [Self::cached_result_metadata] has gone stale.
+    pub(crate) fn contents(&self) -> &str {
+        &self.contents
+    }
+
+    /// Result metadata captured when this statement was prepared. `None` for statements that
+    /// don't return rows (e.g. `INSERT`/`UPDATE`/`DELETE`).
+    ///
+    /// Whenever this is `Some`, EXECUTE frames built by [Self::to_execute_frame] set the
+    /// `SKIP_METADATA` flag, so the server doesn't have to re-send column specs on every
+    /// execution; see `NonErrorQueryResponse::into_query_result_and_paging_state_with_maybe_unknown_coordinator`
+    /// for how the driver falls back to this cached copy when deserializing rows.
+    pub(crate) fn cached_result_metadata(&self) -> Option<&Arc<result::ResultMetadata<'static>>> {
+        self.cached_result_metadata.as_ref()
+    }
+
+    pub(crate) fn set_cached_result_metadata(
+        &mut self,
+        cached_result_metadata: Arc<result::ResultMetadata<'static>>,
+    ) {
+        self.cached_result_metadata = Some(cached_result_metadata);
+    }
+
+    /// Attaches a custom payload to be sent to the server alongside every EXECUTE of this
+    /// statement. See `Query::set_custom_payload` for the general mechanism.
+    pub fn set_custom_payload(&mut self, custom_payload: Option<HashMap<String, Bytes>>) {
+        self.custom_payload = custom_payload;
+    }
+
+    pub fn get_custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.custom_payload.as_ref()
+    }
+
+    /// Builds the EXECUTE frame for this statement.
+    pub(crate) fn to_execute_frame(&self) -> execute::Execute<'_> {
+        execute::Execute {
+            id: self.id.clone(),
+            parameters: Default::default(),
+            skip_metadata: self.cached_result_metadata.is_some(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use scylla_cql::frame::request::query;
+
+/// Text of a CQL statement together with the options that configure how it's executed.
+#[derive(Debug, Clone)]
+pub struct Query {
+    pub(crate) contents: String,
+    custom_payload: Option<HashMap<String, Bytes>>,
+}
+
+impl Query {
+    pub fn new(contents: impl Into<String>) -> Self {
+        Self {
+            contents: contents.into(),
+            custom_payload: None,
+        }
+    }
+
+    /// Attaches a custom payload to be sent to the server alongside this query.
+    ///
+    /// This is the standard CQL v4+ custom-payload mechanism, commonly consumed by
+    /// server-side extensions or proxies. The payload travels at the protocol envelope level,
+    /// alongside (not inside) the QUERY frame built by [Self::to_query_frame].
+    pub fn set_custom_payload(&mut self, custom_payload: Option<HashMap<String, Bytes>>) {
+        self.custom_payload = custom_payload;
+    }
+
+    /// The custom payload previously set with [Self::set_custom_payload], if any.
+    pub fn get_custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.custom_payload.as_ref()
+    }
+
+    /// Builds the QUERY frame for this statement.
+    pub(crate) fn to_query_frame(&self) -> query::Query<'_> {
+        query::Query {
+            contents: query::QueryContents::Borrowed(&self.contents),
+            parameters: Default::default(),
+        }
+    }
+}